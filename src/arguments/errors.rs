@@ -0,0 +1,106 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::process::exit;
+
+/// Errors that can occur while reading or writing the files this application
+/// uses to stage a command and its inputs on disk.
+#[derive(Debug)]
+pub enum FileErr {
+    Open(PathBuf, io::Error),
+    Read(PathBuf, io::Error),
+    Write(PathBuf, io::Error),
+}
+
+impl fmt::Display for FileErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FileErr::Open(ref path, ref why) => write!(f, "unable to open {:?}: {}", path, why),
+            FileErr::Read(ref path, ref why) => write!(f, "unable to read {:?}: {}", path, why),
+            FileErr::Write(ref path, ref why) => write!(f, "unable to write {:?}: {}", path, why),
+        }
+    }
+}
+
+/// Every way that parsing the program's arguments can fail.
+#[derive(Debug)]
+pub enum ParseErr {
+    DelayNaN(usize),
+    DelayNoValue,
+    /// The value given to `-d`/`--delimiter` was not a single byte or a
+    /// recognized `\0`/`\n`/`\t` escape.
+    DelimiterInvalid(String),
+    DelimiterNoValue,
+    File(FileErr),
+    /// The value given to `--halt` did not match the `policy,condition=count`
+    /// grammar (e.g. `never`, `now,fail=1`, `soon,success=3`).
+    HaltInvalid(String),
+    HaltNoValue,
+    InvalidArgument(usize),
+    JoblogNoValue,
+    JobsNoValue,
+    MaxArgsNaN(usize),
+    MaxArgsNoValue,
+    /// The value given to `--max-chars` was not a valid unsigned integer.
+    MaxCharsNaN(usize),
+    MaxCharsNoValue,
+    MemInvalid(usize),
+    MemNoValue,
+    NoArguments,
+    NonTerminated(String),
+    RedirFile(PathBuf),
+    TimeoutNaN(usize),
+    TimeoutNoValue,
+    WorkDirNoValue,
+}
+
+impl fmt::Display for ParseErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseErr::DelayNaN(index) =>
+                write!(f, "argument #{}: --delay value is not a number", index),
+            ParseErr::DelayNoValue => write!(f, "--delay requires a value"),
+            ParseErr::DelimiterInvalid(ref value) =>
+                write!(f, "-d/--delimiter: invalid delimiter: {:?}", value),
+            ParseErr::DelimiterNoValue => write!(f, "-d/--delimiter requires a value"),
+            ParseErr::File(ref why) => write!(f, "{}", why),
+            ParseErr::HaltInvalid(ref value) => write!(f, "--halt: invalid policy: {:?}", value),
+            ParseErr::HaltNoValue => write!(f, "--halt requires a value"),
+            ParseErr::InvalidArgument(index) => write!(f, "argument #{} is invalid", index),
+            ParseErr::JoblogNoValue => write!(f, "--joblog requires a value"),
+            ParseErr::JobsNoValue => write!(f, "-j/--jobs requires a value"),
+            ParseErr::MaxArgsNaN(index) =>
+                write!(f, "argument #{}: -n value is not a number", index),
+            ParseErr::MaxArgsNoValue => write!(f, "-n requires a value"),
+            ParseErr::MaxCharsNaN(index) =>
+                write!(f, "argument #{}: --max-chars value is not a number", index),
+            ParseErr::MaxCharsNoValue => write!(f, "--max-chars requires a value"),
+            ParseErr::MemInvalid(index) =>
+                write!(f, "argument #{}: --mem-free value is invalid", index),
+            ParseErr::MemNoValue => write!(f, "--mem-free requires a value"),
+            ParseErr::NoArguments => write!(f, "no input arguments were supplied"),
+            ParseErr::NonTerminated(ref command) =>
+                write!(f, "the command has an unterminated quote: {}", command),
+            ParseErr::RedirFile(ref path) => write!(f, "invalid redirected input file: {:?}", path),
+            ParseErr::TimeoutNaN(index) =>
+                write!(f, "argument #{}: --timeout value is not a number", index),
+            ParseErr::TimeoutNoValue => write!(f, "--timeout requires a value"),
+            ParseErr::WorkDirNoValue => write!(f, "--tmpdir/--tempdir requires a value"),
+        }
+    }
+}
+
+impl From<FileErr> for ParseErr {
+    fn from(why: FileErr) -> ParseErr { ParseErr::File(why) }
+}
+
+impl ParseErr {
+    /// Prints this error to standard error and exits with a failure status.
+    /// `_arguments` is accepted for parity with callers that may want to
+    /// report the raw invocation later, but isn't needed to describe the
+    /// error itself.
+    pub fn handle(self, _arguments: &[String]) -> ! {
+        eprintln!("parallel: {}", self);
+        exit(1);
+    }
+}
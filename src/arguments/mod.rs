@@ -9,7 +9,6 @@ use std::{
     env,
     fs::{self, create_dir_all},
     io::{self, BufRead, BufReader, BufWriter, Write},
-    num::ParseIntError,
     path::{Path, PathBuf},
     process::exit,
     time::Duration,
@@ -47,6 +46,13 @@ pub const JOBLOG: u16 = 512;
 pub const JOBLOG_8601: u16 = 1024;
 pub const ION_EXISTS: u16 = 2048;
 pub const ZSH_EXISTS: u16 = 32;
+/// Set when `-X`/`--xargs` is passed, grouping many inputs into a single command
+/// invocation instead of spawning one child per input.
+pub const BATCH_MODE: u16 = 4096;
+pub const NO_FD_LIMIT: u16 = 8192;
+/// Set when `--ndjson` is passed (which also enables `--dry-run`), switching the
+/// preview output from one shell line per job to one JSON object per job.
+pub const NDJSON_MODE: u16 = 16384;
 
 /// `Args` is a collection of critical options and arguments that were collected
 /// at startup of the application.
@@ -60,6 +66,42 @@ pub struct Args {
     pub arguments: ArrayVec<[Token; 128]>,
     pub joblog:    Option<String>,
     pub tempdir:   Option<PathBuf>,
+    /// The byte that separates input records when reading from standard input
+    /// or an input file. Defaults to `\n`; `-0`/`--null` and `-d`/`--delimiter`
+    /// override it.
+    pub delimiter: u8,
+    /// The failure-handling policy selected via `--halt`. Defaults to
+    /// `HaltPolicy::Never`, running every job to completion.
+    ///
+    /// Parsing and storage are complete, but nothing reads this field back
+    /// yet: enforcing it means killing outstanding children on `now,...` or
+    /// refusing to launch new ones on `soon,...` from within the dispatch
+    /// loop (`execute::receive_messages`), which isn't part of this tree.
+    /// See `exit_code::JobExit` for the matching half of this gap.
+    pub halt: HaltPolicy,
+}
+
+/// The condition and count that trips a `--halt` policy.
+#[derive(Clone, Copy, PartialEq)]
+pub enum HaltThreshold {
+    /// Trip once this many jobs have failed.
+    Fail(usize),
+    /// Trip once this many jobs have succeeded.
+    Success(usize),
+}
+
+/// Failure-handling policy selected via `--halt`, modeled on xargs' exit
+/// semantics.
+#[derive(Clone, Copy, PartialEq)]
+pub enum HaltPolicy {
+    /// Run every job to completion regardless of failures (the default).
+    Never,
+    /// Once the threshold trips, kill outstanding children and exit
+    /// immediately.
+    Now(HaltThreshold),
+    /// Once the threshold trips, stop launching new jobs but let already
+    /// running jobs finish.
+    Soon(HaltThreshold),
 }
 
 impl Args {
@@ -74,6 +116,8 @@ impl Args {
             timeout:   Duration::from_millis(0),
             joblog:    None,
             tempdir:   None,
+            delimiter: b'\n',
+            halt:      HaltPolicy::Never,
         }
     }
 
@@ -92,6 +136,12 @@ impl Args {
         // If this value is set, input arguments will be grouped into pairs defined by
         // `max_args` value.
         let mut max_args = 0;
+        // If this value is set, groups of input arguments will be flushed before
+        // the combined length of their inputs (plus one separating byte between
+        // each) would exceed `max_chars` bytes, even if `max_args` hasn't been
+        // reached yet. The length of the command itself is not counted. `0`
+        // means unlimited.
+        let mut max_chars = 0;
         // It is important for the custom `InputIterator` to know how many input
         // arguments are to be processed.
         let mut number_of_arguments = 0;
@@ -136,9 +186,14 @@ impl Args {
                             }
                         } else if character == b'n' {
                             max_args = parse_max_args(argument, arguments.get(index), &mut index)?;
+                        } else if character == b'd' {
+                            let val = arguments.get(index).ok_or(ParseErr::DelimiterNoValue)?;
+                            self.delimiter = parse_delimiter(val)?;
+                            index += 1;
                         } else if character != b'-' {
                             for character in argument[1..].bytes() {
                                 match character {
+                                    b'0' => self.delimiter = b'\0',
                                     b'h' => {
                                         println!("{}", man::MAN_PAGE);
                                         exit(0);
@@ -147,6 +202,7 @@ impl Args {
                                     b'q' => quote_enabled = true,
                                     b's' => self.flags |= QUIET_MODE,
                                     b'v' => self.flags |= VERBOSE_MODE,
+                                    b'X' => self.flags |= BATCH_MODE,
                                     _ => {
                                         let stderr = io::stderr();
                                         let _ = writeln!(
@@ -170,6 +226,26 @@ impl Args {
                                 },
                                 "dry-run" => self.flags |= DRY_RUN,
                                 "eta" => self.flags |= ETA + QUIET_MODE,
+                                "halt" => {
+                                    let val = arguments.get(index).ok_or(ParseErr::HaltNoValue)?;
+                                    self.halt = parse_halt(val)?;
+                                    index += 1;
+
+                                    // `self.halt` is parsed and stored, but nothing yet reads
+                                    // it back to act on it (see the doc comment on `Args::halt`
+                                    // for the missing dispatch-loop half of this gap), so
+                                    // accepting the flag silently would make it look like it
+                                    // works when it has no effect on behavior or exit code.
+                                    if self.halt != HaltPolicy::Never {
+                                        let stderr = io::stderr();
+                                        let _ = writeln!(
+                                            stderr.lock(),
+                                            "parallel: warning: --halt is accepted but not yet \
+                                             enforced; all jobs will run to completion regardless \
+                                             of failures"
+                                        );
+                                    }
+                                },
                                 "help" => {
                                     println!("{}", man::MAN_PAGE);
                                     exit(0);
@@ -191,6 +267,13 @@ impl Args {
                                     }
                                     index += 1;
                                 },
+                                "delimiter" => {
+                                    let val =
+                                        arguments.get(index).ok_or(ParseErr::DelimiterNoValue)?;
+                                    self.delimiter = parse_delimiter(val)?;
+                                    index += 1;
+                                },
+                                "null" => self.delimiter = b'\0',
                                 "num-cpu-cores" => {
                                     println!("{}", num_cpus::get());
                                     exit(0);
@@ -203,6 +286,18 @@ impl Args {
                                         .map_err(|_| ParseErr::MaxArgsNaN(index))?;
                                     index += 1;
                                 },
+                                // NOTE: `-s` is already used by this fork for `QUIET_MODE`, so
+                                // `--max-chars` is only exposed in its long form.
+                                "max-chars" => {
+                                    let val =
+                                        arguments.get(index).ok_or(ParseErr::MaxCharsNoValue)?;
+                                    max_chars = val
+                                        .parse::<usize>()
+                                        .map_err(|_| ParseErr::MaxCharsNaN(index))?;
+                                    index += 1;
+                                },
+                                "no-fd-limit" => self.flags |= NO_FD_LIMIT,
+                                "ndjson" => self.flags |= DRY_RUN + NDJSON_MODE,
                                 "mem-free" => {
                                     let val = arguments.get(index).ok_or(ParseErr::MemNoValue)?;
                                     self.memory = parse_memory(val)
@@ -224,6 +319,7 @@ impl Args {
                                     index += 1;
                                 },
                                 "verbose" => self.flags |= VERBOSE_MODE,
+                                "xargs" => self.flags |= BATCH_MODE,
                                 "version" => {
                                     println!("MIT/Rust Parallel {}", env!("CARGO_PKG_VERSION"));
                                     exit(0);
@@ -297,6 +393,7 @@ impl Args {
                     path.to_str()
                         .ok_or_else(|| ParseErr::RedirFile(path.clone()))?,
                     self.flags & INPUTS_ARE_COMMANDS != 0,
+                    self.delimiter,
                 )?;
             } else if let Mode::Command = mode {
                 while let Some(argument) = arguments.get(index) {
@@ -335,6 +432,7 @@ impl Args {
                         &mut current_inputs,
                         &arguments.last().unwrap(),
                         self.flags & INPUTS_ARE_COMMANDS != 0,
+                        self.delimiter,
                     )?;
                 } else {
                     parse_inputs(
@@ -344,6 +442,7 @@ impl Args {
                         &mut lists,
                         &mut mode,
                         self.flags & INPUTS_ARE_COMMANDS != 0,
+                        self.delimiter,
                     )?;
                 }
             } else {
@@ -354,19 +453,32 @@ impl Args {
                     &mut lists,
                     &mut mode,
                     self.flags & INPUTS_ARE_COMMANDS != 0,
+                    self.delimiter,
                 )?;
             }
 
-            number_of_arguments =
-                write_inputs_to_disk(lists, current_inputs, max_args, base_path.clone())?;
+            number_of_arguments = write_inputs_to_disk(
+                lists,
+                current_inputs,
+                max_args,
+                max_chars,
+                base_path.clone(),
+                self.flags & BATCH_MODE != 0,
+            )?;
         } else if let Some(path) = redirection::input_was_redirected() {
             // Read inputs as commands
             let path = path
                 .to_str()
                 .ok_or_else(|| ParseErr::RedirFile(path.clone()))?;
-            file_parse(&mut current_inputs, path, true)?;
-            number_of_arguments =
-                write_inputs_to_disk(lists, current_inputs, max_args, base_path.clone())?;
+            file_parse(&mut current_inputs, path, true, self.delimiter)?;
+            number_of_arguments = write_inputs_to_disk(
+                lists,
+                current_inputs,
+                max_args,
+                max_chars,
+                base_path.clone(),
+                self.flags & BATCH_MODE != 0,
+            )?;
         }
 
         if number_of_arguments == 0 {
@@ -382,9 +494,12 @@ impl Args {
 
             number_of_arguments = write_stdin_to_disk(
                 max_args,
+                max_chars,
                 base_path.clone(),
                 self.flags & INPUTS_ARE_COMMANDS != 0,
                 quote_enabled,
+                self.delimiter,
+                self.flags & BATCH_MODE != 0,
             )?;
         }
 
@@ -455,14 +570,40 @@ fn quote_inputs(input: &str) -> String {
     unsafe { String::from_utf8_unchecked(output) }
 }
 
+/// Reads a single record from `reader`, delimited by `delimiter` rather than
+/// always `\n`, trimming the trailing delimiter so filenames containing
+/// delimiter-adjacent bytes round-trip correctly. Returns `None` at EOF.
+fn read_record<R: BufRead>(reader: &mut R, delimiter: u8) -> Option<io::Result<String>> {
+    let mut buf = Vec::new();
+    match reader.read_until(delimiter, &mut buf) {
+        Ok(0) => None,
+        Ok(_) => {
+            if buf.last() == Some(&delimiter) {
+                buf.pop();
+            }
+            Some(Ok(String::from_utf8_lossy(&buf).into_owned()))
+        },
+        Err(why) => Some(Err(why)),
+    }
+}
+
 /// Write all arguments from standard input to the disk, recording the number of
 /// arguments that were read.
 fn write_stdin_to_disk(
     max_args: usize,
+    max_chars: usize,
     mut unprocessed_path: PathBuf,
     inputs_are_commands: bool,
     quote_enabled: bool,
+    delimiter: u8,
+    batch_mode: bool,
 ) -> Result<usize, ParseErr> {
+    // `-X`/`--xargs` needs to recover the exact inputs that were grouped onto
+    // one line, so a group is joined with `\0` instead of a literal space --
+    // a byte that no record read with `-0`/`--null` can contain -- while
+    // ordinary grouping keeps joining with a space, since there `{}` renders
+    // the whole group as one space-separated piece of command-line text.
+    let separator: u8 = if batch_mode { b'\0' } else { b' ' };
     // Write a message to standard error that inputs are being read from standard
     // input
     let stderr = io::stderr();
@@ -491,8 +632,9 @@ fn write_stdin_to_disk(
         };
 
     let stdin = io::stdin();
-    if max_args < 2 {
-        for line in BufReader::new(stdin.lock()).lines() {
+    if max_args < 2 && max_chars == 0 {
+        let mut reader = BufReader::new(stdin.lock());
+        while let Some(line) = read_record(&mut reader, delimiter) {
             if let Ok(line) = parse_line(line) {
                 if line.is_empty() {
                     continue;
@@ -505,38 +647,60 @@ fn write_stdin_to_disk(
             }
         }
     } else {
-        let mut max_args_index = max_args;
-        for line in BufReader::new(stdin.lock()).lines() {
+        // `--max-chars` groups inputs by a byte budget independently of
+        // `-n`/`--max-args`, so a group is flushed as soon as either limit
+        // would otherwise be exceeded. When `-n` wasn't given, `max_args` is
+        // treated as unlimited and only `max_chars` bounds each group.
+        let effective_max_args = if max_args < 2 { usize::max_value() } else { max_args };
+        let mut current_len = 0;
+        let mut current_count = 0;
+        let mut reader = BufReader::new(stdin.lock());
+        while let Some(line) = read_record(&mut reader, delimiter) {
             if let Ok(line) = parse_line(line) {
                 if line.is_empty() {
                     continue;
                 }
-                if max_args_index == max_args {
-                    max_args_index -= 1;
-                    number_of_arguments += 1;
-                    disk_buffer
-                        .write(line.as_bytes())
-                        .map_err(|why| FileErr::Write(unprocessed_path.clone(), why))?;
-                } else if max_args_index == 1 {
-                    max_args_index = max_args;
+
+                // A single input longer than `max_chars` is still emitted on its own
+                // line; the command itself is left to reject it if it's truly too long.
+                if max_chars != 0 && line.len() > max_chars {
+                    let _ = writeln!(
+                        stderr,
+                        "parallel: warning: input exceeds --max-chars ({} bytes): {}",
+                        max_chars, line
+                    );
+                }
+
+                let separator_len = if current_count == 0 { 0 } else { 1 };
+                let exceeds_chars =
+                    max_chars != 0 && current_count != 0 && current_len + separator_len + line.len() > max_chars;
+                if current_count != 0 && (current_count == effective_max_args || exceeds_chars) {
                     disk_buffer
-                        .write(b" ")
-                        .and_then(|_| disk_buffer.write(line.as_bytes()))
-                        .and_then(|_| disk_buffer.write(b"\n"))
+                        .write(b"\n")
                         .map_err(|why| FileErr::Write(unprocessed_path.clone(), why))?;
-                } else {
-                    max_args_index -= 1;
+                    number_of_arguments += 1;
+                    current_len = 0;
+                    current_count = 0;
+                }
+
+                if current_count != 0 {
                     disk_buffer
-                        .write(b" ")
-                        .and_then(|_| disk_buffer.write(line.as_bytes()))
+                        .write(&[separator])
                         .map_err(|why| FileErr::Write(unprocessed_path.clone(), why))?;
+                    current_len += 1;
                 }
+                disk_buffer
+                    .write(line.as_bytes())
+                    .map_err(|why| FileErr::Write(unprocessed_path.clone(), why))?;
+                current_len += line.len();
+                current_count += 1;
             }
         }
-        if max_args_index != max_args {
+        if current_count != 0 {
             disk_buffer
                 .write(b"\n")
                 .map_err(|why| FileErr::Write(unprocessed_path.clone(), why))?;
+            number_of_arguments += 1;
         }
     }
 
@@ -549,8 +713,14 @@ fn write_inputs_to_disk(
     lists: Vec<Vec<String>>,
     current_inputs: Vec<String>,
     max_args: usize,
+    max_chars: usize,
     mut unprocessed_path: PathBuf,
+    batch_mode: bool,
 ) -> Result<usize, ParseErr> {
+    // See the matching comment in `write_stdin_to_disk`: `-X`/`--xargs` needs
+    // grouped inputs joined with an unambiguous separator so they can be
+    // recovered exactly, instead of the literal space ordinary grouping uses.
+    let separator: char = if batch_mode { '\u{0}' } else { ' ' };
     unprocessed_path.push("unprocessed");
     let disk_buffer = fs::OpenOptions::new()
         .truncate(true)
@@ -574,95 +744,80 @@ fn write_inputs_to_disk(
         // Create a `Permutator` with the &[&[&str]] as the input.
         let mut permutator = Permutator::new(&list_array[..]);
 
-        // Generate the first permutation's buffer
+        // Groups permutation tuples the same way the other branches group
+        // plain inputs: flush as soon as either `-n`/`--max-args` or
+        // `--max-chars` would otherwise be exceeded. `max_args` is treated as
+        // unlimited when `-n` wasn't given, so `--max-chars` alone is enough
+        // to bound a group here too. With neither limit given, each permutation
+        // is written on its own line.
+        let effective_max_args = if max_args >= 2 {
+            max_args
+        } else if max_chars != 0 {
+            usize::max_value()
+        } else {
+            1
+        };
+        let mut current_len = 0;
+        let mut current_count = 0;
+        let mut permutation = String::new();
         let mut permutation_buffer = permutator.next().unwrap();
-        {
-            let mut iter = permutation_buffer.iter();
-            disk_buffer
-                .write(iter.next().unwrap().as_bytes())
-                .map_err(|why| FileErr::Write(unprocessed_path.clone(), why))?;
-            for element in iter {
-                disk_buffer
-                    .write(b" ")
-                    .and_then(|_| disk_buffer.write(element.as_bytes()))
-                    .map_err(|why| FileErr::Write(unprocessed_path.clone(), why))?;
-            }
-
-            number_of_arguments += 1;
-        }
-
-        if max_args < 2 {
-            disk_buffer
-                .write(b"\n")
-                .map_err(|why| FileErr::Write(unprocessed_path.clone(), why))?;
-            while permutator.next_with_buffer(&mut permutation_buffer) {
+        loop {
+            permutation.clear();
+            {
                 let mut iter = permutation_buffer.iter();
-                disk_buffer
-                    .write(iter.next().unwrap().as_bytes())
-                    .map_err(|why| FileErr::Write(unprocessed_path.clone(), why))?;
+                permutation.push_str(iter.next().unwrap());
                 for element in iter {
-                    disk_buffer
-                        .write(b" ")
-                        .and_then(|_| disk_buffer.write(element.as_bytes()))
-                        .map_err(|why| FileErr::Write(unprocessed_path.clone(), why))?;
+                    permutation.push(separator);
+                    permutation.push_str(element);
                 }
+            }
+
+            if max_chars != 0 && permutation.len() > max_chars {
+                let stderr = io::stderr();
+                let _ = writeln!(
+                    stderr.lock(),
+                    "parallel: warning: input exceeds --max-chars ({} bytes): {}",
+                    max_chars, permutation
+                );
+            }
+
+            let separator_len = if current_count == 0 { 0 } else { 1 };
+            let exceeds_chars = max_chars != 0
+                && current_count != 0
+                && current_len + separator_len + permutation.len() > max_chars;
+            if current_count != 0 && (current_count == effective_max_args || exceeds_chars) {
                 disk_buffer
                     .write(b"\n")
                     .map_err(|why| FileErr::Write(unprocessed_path.clone(), why))?;
                 number_of_arguments += 1;
+                current_len = 0;
+                current_count = 0;
             }
-        } else {
-            let mut max_args_index = max_args - 1;
-            while permutator.next_with_buffer(&mut permutation_buffer) {
-                let mut iter = permutation_buffer.iter();
-                if max_args_index == max_args {
-                    max_args_index -= 1;
-                    number_of_arguments += 1;
 
-                    disk_buffer
-                        .write(iter.next().unwrap().as_bytes())
-                        .map_err(|why| FileErr::Write(unprocessed_path.clone(), why))?;
-
-                    for element in iter {
-                        disk_buffer
-                            .write(b" ")
-                            .and_then(|_| disk_buffer.write(element.as_bytes()))
-                            .map_err(|why| FileErr::Write(unprocessed_path.clone(), why))?;
-                    }
-                } else if max_args_index == 1 {
-                    max_args_index = max_args;
-                    disk_buffer
-                        .write(b" ")
-                        .and_then(|_| disk_buffer.write(iter.next().unwrap().as_bytes()))
-                        .map_err(|why| FileErr::Write(unprocessed_path.clone(), why))?;
-
-                    for element in iter {
-                        disk_buffer
-                            .write(b" ")
-                            .and_then(|_| disk_buffer.write(element.as_bytes()))
-                            .map_err(|why| FileErr::Write(unprocessed_path.clone(), why))?;
-                    }
-
-                    disk_buffer
-                        .write(b"\n")
-                        .map_err(|why| FileErr::Write(unprocessed_path.clone(), why))?;
-                } else {
-                    max_args_index -= 1;
-                    disk_buffer
-                        .write(b" ")
-                        .and_then(|_| disk_buffer.write(iter.next().unwrap().as_bytes()))
-                        .map_err(|why| FileErr::Write(unprocessed_path.clone(), why))?;
+            if current_count != 0 {
+                disk_buffer
+                    .write(&[separator as u8])
+                    .map_err(|why| FileErr::Write(unprocessed_path.clone(), why))?;
+                current_len += 1;
+            }
+            disk_buffer
+                .write(permutation.as_bytes())
+                .map_err(|why| FileErr::Write(unprocessed_path.clone(), why))?;
+            current_len += permutation.len();
+            current_count += 1;
 
-                    for element in iter {
-                        disk_buffer
-                            .write(b" ")
-                            .and_then(|_| disk_buffer.write(element.as_bytes()))
-                            .map_err(|why| FileErr::Write(unprocessed_path.clone(), why))?;
-                    }
-                }
+            if !permutator.next_with_buffer(&mut permutation_buffer) {
+                break;
             }
         }
-    } else if max_args < 2 {
+
+        if current_count != 0 {
+            disk_buffer
+                .write(b"\n")
+                .map_err(|why| FileErr::Write(unprocessed_path.clone(), why))?;
+            number_of_arguments += 1;
+        }
+    } else if max_args < 2 && max_chars == 0 {
         for input in current_inputs {
             disk_buffer
                 .write(input.as_bytes())
@@ -671,22 +826,56 @@ fn write_inputs_to_disk(
             number_of_arguments += 1;
         }
     } else {
-        for chunk in current_inputs.chunks(max_args) {
-            let max_index = chunk.len() - 1;
-            let mut index = 0;
-            number_of_arguments += 1;
+        // Flush the current group as soon as either `max_args` or `max_chars`
+        // would otherwise be exceeded, rather than always waiting for a fixed
+        // count of inputs. A single input longer than `max_chars` is still
+        // emitted on its own line. `max_args` is treated as unlimited when
+        // `-n` wasn't given, so `--max-chars` alone is enough to bound a group.
+        let effective_max_args = if max_args < 2 { usize::max_value() } else { max_args };
+        let stderr = io::stderr();
+        let mut stderr = stderr.lock();
+        let mut current_len = 0;
+        let mut current_count = 0;
+
+        for input in &current_inputs {
+            if max_chars != 0 && input.len() > max_chars {
+                let _ = writeln!(
+                    stderr,
+                    "parallel: warning: input exceeds --max-chars ({} bytes): {}",
+                    max_chars, input
+                );
+            }
 
-            while index != max_index {
+            let separator_len = if current_count == 0 { 0 } else { 1 };
+            let exceeds_chars =
+                max_chars != 0 && current_count != 0 && current_len + separator_len + input.len() > max_chars;
+            if current_count != 0 && (current_count == effective_max_args || exceeds_chars) {
                 disk_buffer
-                    .write(chunk[index].as_bytes())
-                    .and_then(|_| disk_buffer.write(b" "))
+                    .write(b"\n")
                     .map_err(|why| FileErr::Write(unprocessed_path.clone(), why))?;
-                index += 1;
+                number_of_arguments += 1;
+                current_len = 0;
+                current_count = 0;
+            }
+
+            if current_count != 0 {
+                disk_buffer
+                    .write(&[separator as u8])
+                    .map_err(|why| FileErr::Write(unprocessed_path.clone(), why))?;
+                current_len += 1;
             }
             disk_buffer
-                .write(chunk[max_index].as_bytes())
-                .and_then(|_| disk_buffer.write(b"\n"))
+                .write(input.as_bytes())
+                .map_err(|why| FileErr::Write(unprocessed_path.clone(), why))?;
+            current_len += input.len();
+            current_count += 1;
+        }
+
+        if current_count != 0 {
+            disk_buffer
+                .write(b"\n")
                 .map_err(|why| FileErr::Write(unprocessed_path.clone(), why))?;
+            number_of_arguments += 1;
         }
     }
     Ok(number_of_arguments)
@@ -701,6 +890,7 @@ fn parse_inputs(
     lists: &mut Vec<Vec<String>>,
     mode: &mut Mode,
     inputs_are_commands: bool,
+    delimiter: u8,
 ) -> Result<(), ParseErr> {
     let append_list = &mut Vec::new();
 
@@ -749,8 +939,8 @@ fn parse_inputs(
                 },
                 Mode::Inputs => current_inputs.push(argument.clone()),
                 Mode::InputsAppend => append_list.push(argument.clone()),
-                Mode::Files => file_parse(current_inputs, argument, inputs_are_commands)?,
-                Mode::FilesAppend => file_parse(append_list, argument, inputs_are_commands)?,
+                Mode::Files => file_parse(current_inputs, argument, inputs_are_commands, delimiter)?,
+                Mode::FilesAppend => file_parse(append_list, argument, inputs_are_commands, delimiter)?,
                 _ => unreachable!(),
             },
         }
@@ -804,21 +994,90 @@ fn merge_lists(original: &mut Vec<String>, append: &mut Vec<String>) {
 
 /// When the `--memfree` option has been selected, this will attempt to parse
 /// the unit's value, multiplying that value by the unit's multiplier.
-fn parse_memory(input: &str) -> Result<u64, ParseIntError> {
-    let result = match input.bytes().last().unwrap() {
-        b'k' => &input[..input.len() - 1].parse::<u64>()? * 1_000,
-        b'K' => &input[..input.len() - 1].parse::<u64>()? * 1_024,
-        b'm' => &input[..input.len() - 1].parse::<u64>()? * 1_000_000,
-        b'M' => &input[..input.len() - 1].parse::<u64>()? * 1_048_576,
-        b'g' => &input[..input.len() - 1].parse::<u64>()? * 1_000_000_000,
-        b'G' => &input[..input.len() - 1].parse::<u64>()? * 1_073_741_824,
-        b't' => &input[..input.len() - 1].parse::<u64>()? * 1_000_000_000_000,
-        b'T' => &input[..input.len() - 1].parse::<u64>()? * 1_099_511_627_776,
-        b'p' => &input[..input.len() - 1].parse::<u64>()? * 1_000_000_000_000_000,
-        b'P' => &input[..input.len() - 1].parse::<u64>()? * 1_125_899_906_842_624,
-        _ => input.parse::<u64>()?,
+/// Parses a `numfmt`-style memory quantity, such as `500`, `1.5G`, `4MiB`, or
+/// `2kB`. A bare letter (`K`, `M`, `G`, `T`, `P`) is kept for backward
+/// compatibility with this fork's historical shorthand and is treated as a
+/// binary (1024-based) unit, matching the behavior of the equivalent `iB`
+/// suffix. An explicit `i` before the `B` (`KiB`, `MiB`, ...) always means
+/// binary, while a suffix of just `B` (`kB`, `MB`, ...) means decimal
+/// (1000-based), following `numfmt`'s own convention. The numeric portion may
+/// be fractional.
+fn parse_memory(input: &str) -> Result<u64, ()> {
+    let suffix_start = input
+        .bytes()
+        .position(|byte| !(byte.is_ascii_digit() || byte == b'.'))
+        .unwrap_or_else(|| input.len());
+    let (number, suffix) = input.split_at(suffix_start);
+    let number = number.parse::<f64>().map_err(|_| ())?;
+
+    let multiplier: f64 = match suffix {
+        "" => 1.0,
+        "k" | "K" => 1_024.0,
+        "m" | "M" => 1_048_576.0,
+        "g" | "G" => 1_073_741_824.0,
+        "t" | "T" => 1_099_511_627_776.0,
+        "p" | "P" => 1_125_899_906_842_624.0,
+        "kB" => 1_000.0,
+        "KiB" => 1_024.0,
+        "MB" => 1_000_000.0,
+        "MiB" => 1_048_576.0,
+        "GB" => 1_000_000_000.0,
+        "GiB" => 1_073_741_824.0,
+        "TB" => 1_000_000_000_000.0,
+        "TiB" => 1_099_511_627_776.0,
+        "PB" => 1_000_000_000_000_000.0,
+        "PiB" => 1_125_899_906_842_624.0,
+        _ => return Err(()),
+    };
+
+    Ok((number * multiplier) as u64)
+}
+
+/// Parses the `policy,condition=count` grammar accepted by `--halt`, e.g.
+/// `never`, `now,fail=1`, or `soon,fail=5`.
+fn parse_halt(input: &str) -> Result<HaltPolicy, ParseErr> {
+    let mut parts = input.splitn(2, ',');
+    let policy = parts.next().unwrap_or("");
+
+    if policy == "never" {
+        return Ok(HaltPolicy::Never);
+    }
+
+    if policy != "now" && policy != "soon" {
+        return Err(ParseErr::HaltInvalid(String::from(input)));
+    }
+
+    let condition = parts
+        .next()
+        .ok_or_else(|| ParseErr::HaltInvalid(String::from(input)))?;
+    let mut condition_parts = condition.splitn(2, '=');
+    let kind = condition_parts.next().unwrap_or("");
+    let count = condition_parts
+        .next()
+        .ok_or_else(|| ParseErr::HaltInvalid(String::from(input)))?
+        .parse::<usize>()
+        .map_err(|_| ParseErr::HaltInvalid(String::from(input)))?;
+
+    let threshold = match kind {
+        "fail" => HaltThreshold::Fail(count),
+        "success" => HaltThreshold::Success(count),
+        _ => return Err(ParseErr::HaltInvalid(String::from(input))),
     };
-    Ok(result)
+
+    Ok(if policy == "now" { HaltPolicy::Now(threshold) } else { HaltPolicy::Soon(threshold) })
+}
+
+/// Parses the `-d`/`--delimiter` value into the byte that separates input
+/// records, accepting either a single literal byte or a C-style escape such
+/// as `\t` or `\0`.
+fn parse_delimiter(input: &str) -> Result<u8, ParseErr> {
+    match input.as_bytes() {
+        [byte] => Ok(*byte),
+        [b'\\', b'0'] => Ok(b'\0'),
+        [b'\\', b'n'] => Ok(b'\n'),
+        [b'\\', b't'] => Ok(b'\t'),
+        _ => Err(ParseErr::DelimiterInvalid(String::from(input))),
+    }
 }
 
 /// Parses the jobs value, and optionally increments the index if necessary.
@@ -842,11 +1101,26 @@ fn file_parse<P: AsRef<Path>>(
     inputs: &mut Vec<String>,
     path: P,
     inputs_are_commands: bool,
+    delimiter: u8,
 ) -> Result<(), ParseErr> {
     let path = path.as_ref();
-    let file =
-        fs::File::open(path).map_err(|err| ParseErr::File(FileErr::Open(path.to_owned(), err)))?;
-    for line in BufReader::new(file).lines().flatten() {
+
+    // A path of `-` reads from standard input instead of a real file, so that
+    // `parallel cmd :::: -` pipelines work. Named FIFOs already work through
+    // the regular `File::open` path.
+    let reader: Box<dyn BufRead> = if path == Path::new("-") {
+        Box::new(BufReader::new(io::stdin()))
+    } else {
+        let file = fs::File::open(path)
+            .map_err(|err| ParseErr::File(FileErr::Open(path.to_owned(), err)))?;
+        Box::new(BufReader::new(file))
+    };
+
+    // Records are split on `delimiter` rather than always assuming newlines, so
+    // that `--null`/`-0` can be used to safely read lists of filenames that may
+    // themselves contain newlines.
+    for record in reader.split(delimiter).flatten() {
+        let line = String::from_utf8_lossy(&record).into_owned();
         if !line.is_empty() && !line.starts_with('#') {
             if inputs_are_commands {
                 inputs.push(quote_command(&line));
@@ -857,3 +1131,39 @@ fn file_parse<P: AsRef<Path>>(
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::parse_memory;
+
+    #[test]
+    fn parse_memory_bare_numbers() {
+        assert_eq!(parse_memory("0").unwrap(), 0);
+        assert_eq!(parse_memory("512").unwrap(), 512);
+    }
+
+    #[test]
+    fn parse_memory_binary_shorthand() {
+        assert_eq!(parse_memory("1K").unwrap(), 1_024);
+        assert_eq!(parse_memory("1k").unwrap(), 1_024);
+        assert_eq!(parse_memory("2G").unwrap(), 2 * 1_073_741_824);
+    }
+
+    #[test]
+    fn parse_memory_iec_and_si_suffixes() {
+        assert_eq!(parse_memory("1KiB").unwrap(), 1_024);
+        assert_eq!(parse_memory("1kB").unwrap(), 1_000);
+        assert_eq!(parse_memory("1MiB").unwrap(), 1_048_576);
+        assert_eq!(parse_memory("1MB").unwrap(), 1_000_000);
+    }
+
+    #[test]
+    fn parse_memory_fractional() {
+        assert_eq!(parse_memory("1.5G").unwrap(), 1_610_612_736);
+    }
+
+    #[test]
+    fn parse_memory_rejects_unknown_suffix() {
+        assert!(parse_memory("1QB").is_err());
+    }
+}
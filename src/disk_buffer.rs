@@ -0,0 +1,75 @@
+use std::{
+    io::{self, Read},
+    mem::MaybeUninit,
+    path::{Path, PathBuf},
+    slice,
+};
+
+/// The size, in bytes, of the on-disk read buffer used by `DiskBufferReader`.
+pub const BUFFER_SIZE: usize = 64 * 1024;
+
+/// Reads a file (or other `Read` source) a chunk at a time into a fixed-size
+/// buffer. The buffer is backed by uninitialized memory rather than a
+/// zeroed allocation, since it is immediately overwritten by `Read::read`
+/// before any of it is exposed -- there is no reason to pay for zeroing 64
+/// KiB on every refill just to discard it.
+pub struct DiskBufferReader<IO: Read> {
+    pub path:     PathBuf,
+    pub capacity: usize,
+    buffer:       Box<MaybeUninit<[u8; BUFFER_SIZE]>>,
+    file:         IO,
+}
+
+impl<IO: Read> DiskBufferReader<IO> {
+    pub fn new(path: &Path, file: IO) -> DiskBufferReader<IO> {
+        DiskBufferReader {
+            path:     path.to_owned(),
+            capacity: 0,
+            buffer:   Box::new(MaybeUninit::uninit()),
+            file,
+        }
+    }
+
+    /// Returns the portion of the buffer that has actually been filled by a
+    /// prior call to `buffer`. Bytes beyond `capacity` are never exposed, as
+    /// they may still be uninitialized.
+    pub fn data(&self) -> &[u8] {
+        let initialized = self.buffer.as_ptr() as *const u8;
+        unsafe { slice::from_raw_parts(initialized, self.capacity) }
+    }
+
+    /// Refills the buffer, preserving the trailing bytes after `kept` (the
+    /// portion of the previous read that has not yet been consumed) by
+    /// shifting them to the front, then reading new bytes from `file`
+    /// directly into the remaining, possibly-uninitialized, space.
+    pub fn buffer(&mut self, kept: usize) -> io::Result<()> {
+        let raw = self.buffer.as_mut_ptr() as *mut u8;
+
+        let tail = self.capacity - kept;
+        if tail > 0 {
+            unsafe { std::ptr::copy(raw.add(kept), raw, tail) };
+        }
+
+        let mut filled = tail;
+        loop {
+            if filled == BUFFER_SIZE {
+                break;
+            }
+
+            // Safe because `u8` has no validity invariant beyond being a
+            // byte, so handing `Read::read` a view over not-yet-initialized
+            // memory is sound -- it will only ever write valid bytes into it.
+            let spare = unsafe { slice::from_raw_parts_mut(raw.add(filled), BUFFER_SIZE - filled) };
+
+            match self.file.read(spare) {
+                Ok(0) => break,
+                Ok(n) => filled += n,
+                Err(ref why) if why.kind() == io::ErrorKind::Interrupted => continue,
+                Err(why) => return Err(why),
+            }
+        }
+
+        self.capacity = filled;
+        Ok(())
+    }
+}
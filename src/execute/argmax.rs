@@ -0,0 +1,74 @@
+use std::env;
+use std::ffi::OsStr;
+use std::mem::size_of;
+
+/// Size in bytes of a single argv/envp pointer slot, used when estimating how
+/// much of `ARG_MAX` a command line will consume.
+const POINTER_SIZE: usize = size_of::<usize>();
+
+/// Conservative fallback budget for platforms where `_SC_ARG_MAX` cannot be
+/// queried.
+const FALLBACK_ARG_MAX: usize = 128 * 1024;
+
+/// Queries the kernel's maximum argument length, falling back to a
+/// conservative constant where `sysconf(_SC_ARG_MAX)` is unavailable.
+#[cfg(unix)]
+fn query_arg_max() -> usize {
+    let value = unsafe { libc::sysconf(libc::_SC_ARG_MAX) };
+    if value > 0 {
+        value as usize
+    } else {
+        FALLBACK_ARG_MAX
+    }
+}
+
+#[cfg(not(unix))]
+fn query_arg_max() -> usize { FALLBACK_ARG_MAX }
+
+/// Computes the number of `ARG_MAX` bytes left over for batched inputs, after
+/// reserving space for the current environment block and the command's fixed
+/// leading arguments (the executable and any literal flags before the
+/// placeholder).
+pub fn argv_budget<S: AsRef<OsStr>>(fixed_args: &[S]) -> usize {
+    let arg_max = query_arg_max();
+
+    let env_size: usize = env::vars_os()
+        .map(|(key, value)| key.len() + 1 + value.len() + 1 + POINTER_SIZE)
+        .sum();
+
+    let fixed_size: usize = fixed_args
+        .iter()
+        .map(|arg| arg.as_ref().len() + 1 + POINTER_SIZE)
+        .sum();
+
+    arg_max.saturating_sub(env_size).saturating_sub(fixed_size)
+}
+
+/// Accumulates inputs into `ARG_MAX`-sized batches, invoking `on_flush` with
+/// the accumulated slice whenever appending the next input would exceed
+/// `budget`. Always flushes at least one input per invocation, even if a
+/// single input alone exceeds the budget, leaving the OS to reject an
+/// over-long command line rather than looping forever.
+pub fn chunk_by_argmax<'a, I, F>(inputs: I, budget: usize, mut on_flush: F)
+where
+    I: IntoIterator<Item = &'a String>,
+    F: FnMut(&[&'a String]),
+{
+    let mut batch: Vec<&'a String> = Vec::new();
+    let mut used = 0usize;
+
+    for input in inputs {
+        let cost = input.len() + 1 + POINTER_SIZE;
+        if !batch.is_empty() && used + cost > budget {
+            on_flush(&batch);
+            batch.clear();
+            used = 0;
+        }
+        batch.push(input);
+        used += cost;
+    }
+
+    if !batch.is_empty() {
+        on_flush(&batch);
+    }
+}
@@ -1,8 +1,11 @@
 use super::argument_splitter::ArgumentSplitter;
+use super::argmax;
 use arguments;
+use quoting;
 use std::{
+    borrow::Cow,
     convert::AsRef,
-    ffi::OsStr,
+    ffi::{OsStr, OsString},
     io::{self, Write},
     process::{Child, Command, Stdio},
     str,
@@ -11,11 +14,33 @@ use tokenizer::*;
 
 pub enum CommandErr {
     IO(io::Error),
+    Batch(BatchErr),
+}
+
+/// Errors that can occur while validating a batch (`-X`/`--xargs`) command
+/// template, where many inputs are grouped into a single invocation rather
+/// than one invocation per input.
+pub enum BatchErr {
+    /// A batch template may contain at most one placeholder token, since
+    /// there would otherwise be no sensible way to expand the input group.
+    MultiplePlaceholders,
+    /// The first token of a batch template must be a fixed `Token::Argument`
+    /// naming the executable to run; it cannot itself be a placeholder.
+    NoExecutable,
+    /// A per-input placeholder (`{.}`, `{/}`, `{//}`, `{/.}`, `{#}`, `{%}`,
+    /// etc.) has no single resolved value across a whole batch of inputs,
+    /// unlike bare `{}`, and so isn't supported in a batch template. Resolved
+    /// numbered tokens (`{1}`, `{2.}`, etc.) are unaffected -- they already
+    /// name one specific, batch-wide-constant value and are handled as a
+    /// `Token::Argument` like literal template text.
+    UnsupportedToken,
 }
 
 /// If no placeholder tokens are in use, then the input will be appended at the
-/// end of the the command.
-pub fn append_argument(arguments: &mut String, command_template: &[Token], input: &str) {
+/// end of the the command. When `quote` is set, the input is POSIX
+/// single-quoted first, since it is about to be handed to a `sh -c` command
+/// line rather than passed directly as an argv element.
+pub fn append_argument(arguments: &mut String, command_template: &[Token], input: &str, quote: bool) {
     let placeholder_exists = command_template.iter().any(|x| {
         matches!(
             *x,
@@ -32,10 +57,83 @@ pub fn append_argument(arguments: &mut String, command_template: &[Token], input
         // If no placeholder tokens are in use, the user probably wants to infer one.
     if !placeholder_exists {
         arguments.push(' ');
-        arguments.push_str(input);
+        push_value(arguments, input, quote);
     }
 }
 
+/// Appends `value` to `arguments`, POSIX single-quoting it first when `quote`
+/// is set. Used to escape every runtime-substituted value on the `sh -c`
+/// path, while literal template text is left untouched so intentional shell
+/// syntax authored by the user keeps working.
+fn push_value(arguments: &mut String, value: &str, quote: bool) {
+    if quote {
+        arguments.push_str(&quoting::escape(value));
+    } else {
+        arguments.push_str(value);
+    }
+}
+
+/// Builds an argument vector for batch (`-X`/`--xargs`) execution, where a
+/// single `{}` placeholder expands to every input in the group (each as its
+/// own argv element) instead of spawning one child per input. When no
+/// placeholder is present, the entire group is appended after the fixed
+/// arguments, mirroring `append_argument`'s single-input behavior.
+pub fn build_argv_batch(command_template: &[Token], inputs: &[String]) -> Result<Vec<OsString>, BatchErr> {
+    if command_template.iter().filter(|token| matches!(**token, Token::Placeholder)).count() > 1 {
+        return Err(BatchErr::MultiplePlaceholders);
+    }
+
+    match command_template.first() {
+        Some(&Token::Argument(_)) => (),
+        _ => return Err(BatchErr::NoExecutable),
+    }
+
+    if command_template.iter().any(|token| {
+        matches!(
+            *token,
+            Token::Basename
+                | Token::BaseAndExt
+                | Token::BaseAndSuffix(_)
+                | Token::Dirname
+                | Token::Job
+                | Token::RemoveExtension
+                | Token::RemoveSuffix(_)
+                | Token::Slot
+        )
+    }) {
+        return Err(BatchErr::UnsupportedToken);
+    }
+
+    let mut argv = Vec::new();
+    let mut placeholder_exists = false;
+
+    for arg in command_template {
+        match *arg {
+            // Literal template text may contain several space-separated words and is
+            // split into one argv element per word, same as `build_argv`. A resolved
+            // numbered-token substitution (`{1}`, `{2.}`, etc.) is a `Cow::Owned`
+            // `Token::Argument` carrying one already-resolved value, which must stay
+            // intact as a single argv element regardless of what it contains.
+            Token::Argument(Cow::Borrowed(arg)) => argv.extend(ArgumentSplitter::new(arg).map(OsString::from)),
+            Token::Argument(Cow::Owned(ref arg)) => argv.push(OsString::from(arg.as_str())),
+            Token::Placeholder => {
+                placeholder_exists = true;
+                argv.extend(inputs.iter().map(OsString::from));
+            },
+            // Per-input placeholders (basename, dirname, job id, slot, etc.) have no
+            // single resolved value across a whole batch, so they are rejected above
+            // rather than silently discarded here.
+            _ => (),
+        }
+    }
+
+    if !placeholder_exists {
+        argv.extend(inputs.iter().map(OsString::from));
+    }
+
+    Ok(argv)
+}
+
 /// A structure for generating commands to be executed.
 pub struct ParallelCommand<'a> {
     pub slot_no:          &'a str,
@@ -49,11 +147,28 @@ pub struct ParallelCommand<'a> {
 impl<'a> ParallelCommand<'a> {
     /// Builds and execute commands based on given flags, supplied inputs and
     /// token arguments.
+    ///
+    /// Note that `-X`/`--xargs` (`BATCH_MODE`) is handled separately by
+    /// `exec_batch`, which can spawn more than one child when a batch exceeds
+    /// `ARG_MAX` and so cannot be represented by this method's single-`Child`
+    /// return type. The dispatch layer that drives job execution must check
+    /// `BATCH_MODE` itself and call `exec_batch` directly rather than `exec`;
+    /// no such dispatch layer exists yet in this tree (see `execute::mod`,
+    /// `execute::pipe`), so this is currently unreachable from a real caller.
     pub fn exec(&self, arguments: &mut String) -> Result<Child, CommandErr> {
+        // When a shell isn't required to execute the command, build the argument
+        // vector directly from the token stream and hand it straight to `Command`,
+        // so no substituted value is ever torn apart by a second round of parsing.
+        if self.flags & arguments::PIPE_IS_ENABLED == 0 && self.flags & arguments::SHELL_ENABLED == 0 {
+            let argv = self.build_argv();
+            return spawn_argv(&argv, self.flags).map_err(CommandErr::IO);
+        }
+
         self.build_arguments(arguments);
 
         if self.flags & arguments::PIPE_IS_ENABLED == 0 {
-            append_argument(arguments, self.command_template, self.input);
+            let quote = self.flags & arguments::SHELL_ENABLED != 0;
+            append_argument(arguments, self.command_template, self.input, quote);
             get_command_output(arguments.as_str(), self.flags).map_err(CommandErr::IO)
         } else {
             let mut child =
@@ -75,6 +190,65 @@ impl<'a> ParallelCommand<'a> {
         }
     }
 
+    /// Executes a batch (`-X`/`--xargs`) command: `self.input` holds several
+    /// inputs joined by `\0`, already grouped by `-n`/`--max-args` at the
+    /// parsing stage (see `write_stdin_to_disk`/`write_inputs_to_disk` in
+    /// `arguments::mod`, which join batch groups with `\0` instead of a
+    /// literal space precisely so that an input containing whitespace can be
+    /// recovered exactly here, rather than torn into extra argv elements).
+    /// Splits them back apart, then hands them to `argmax::chunk_by_argmax`,
+    /// which breaks the group into as many `ARG_MAX`-sized chunks as needed
+    /// rather than one. A child is spawned per chunk -- mirroring what `xargs`
+    /// itself does when a single invocation would overflow `ARG_MAX` -- so
+    /// that every input is still executed, just split across more than one
+    /// invocation of the command instead of silently dropped.
+    ///
+    /// Meant to be called from the dispatch loop that drives job execution,
+    /// checking `BATCH_MODE` itself rather than going through `exec`, since
+    /// `exec`'s single-`Child` return type can't represent multiple spawned
+    /// children. That dispatch loop lives in `execute::mod`/`execute::pipe`,
+    /// neither of which exist in this tree yet, so nothing calls this today.
+    /// `#[allow(dead_code)]` documents that gap rather than hiding it; remove
+    /// it once the dispatch loop is wired up to call this directly.
+    #[allow(dead_code)]
+    pub fn exec_batch(&self) -> Result<Vec<Child>, CommandErr> {
+        let inputs: Vec<String> = self.input.split('\u{0}').map(String::from).collect();
+
+        let fixed_args: Vec<&str> = self
+            .command_template
+            .iter()
+            .flat_map(|token| match *token {
+                Token::Argument(ref arg) => ArgumentSplitter::new(arg).collect(),
+                _ => Vec::new(),
+            })
+            .collect();
+        let budget = argmax::argv_budget(&fixed_args);
+
+        let mut children = Vec::new();
+        let mut spawn_err = None;
+        argmax::chunk_by_argmax(&inputs, budget, |chunk| {
+            if spawn_err.is_some() {
+                return;
+            }
+
+            let chunk: Vec<String> = chunk.iter().map(|input| (*input).clone()).collect();
+            let result = build_argv_batch(self.command_template, &chunk)
+                .map_err(CommandErr::Batch)
+                .and_then(|argv| spawn_argv(&argv, self.flags).map_err(CommandErr::IO));
+
+            match result {
+                Ok(child) => children.push(child),
+                Err(why) => spawn_err = Some(why),
+            }
+        });
+
+        if let Some(why) = spawn_err {
+            return Err(why);
+        }
+
+        Ok(children)
+    }
+
     /// Builds arguments using the `tokens` template with the current `input`
     /// value. The arguments will be stored within a `Vec<String>`
     pub fn build_arguments(&self, arguments: &mut String) {
@@ -91,26 +265,181 @@ impl<'a> ParallelCommand<'a> {
                 }
             }
         } else {
+            // When the command is about to be handed to `sh -c`, every dynamically
+            // substituted value must be single-quoted so that spaces, quotes, `$`,
+            // backticks, and `;` in an input can't break the command or inject
+            // additional shell syntax. Literal template text is left untouched, since
+            // it may be intentional shell syntax authored by the user.
+            let quote = self.flags & arguments::SHELL_ENABLED != 0;
             for arg in self.command_template {
                 match *arg {
                     Token::Argument(ref arg) => arguments.push_str(arg),
-                    Token::Basename => arguments.push_str(basename(self.input)),
-                    Token::BaseAndExt => arguments.push_str(basename(remove_extension(self.input))),
+                    Token::Basename => push_value(arguments, basename(self.input), quote),
+                    Token::BaseAndExt =>
+                        push_value(arguments, basename(remove_extension(self.input)), quote),
                     Token::BaseAndSuffix(pat) =>
-                        arguments.push_str(basename(remove_pattern(self.input, pat))),
-                    Token::Dirname => arguments.push_str(dirname(self.input)),
+                        push_value(arguments, basename(remove_pattern(self.input, pat)), quote),
+                    Token::Dirname => push_value(arguments, dirname(self.input), quote),
                     Token::Job =>
                         for character in self.job_no {
                             arguments.push(*character as char);
                         },
-                    Token::Placeholder => arguments.push_str(self.input),
-                    Token::RemoveExtension => arguments.push_str(remove_extension(self.input)),
-                    Token::RemoveSuffix(pat) => arguments.push_str(remove_pattern(self.input, pat)),
-                    Token::Slot => arguments.push_str(self.slot_no),
+                    Token::Placeholder => push_value(arguments, self.input, quote),
+                    Token::RemoveExtension => push_value(arguments, remove_extension(self.input), quote),
+                    Token::RemoveSuffix(pat) => push_value(arguments, remove_pattern(self.input, pat), quote),
+                    Token::Slot => push_value(arguments, self.slot_no, quote),
                 }
             }
         }
     }
+
+    /// Builds an argument vector directly from the token stream, rather than
+    /// concatenating everything into a single `String` and re-splitting it later.
+    /// Every literal `Token::Argument` (`Cow::Borrowed`, authored template text) is
+    /// split into fixed argv words, but each runtime substitution -- `Placeholder`,
+    /// `Basename`, `Dirname`, `RemoveExtension`, a resolved numbered token
+    /// (`Token::Argument(Cow::Owned(_))`), etc. -- is pushed as exactly one
+    /// argument element regardless of its contents, so substituted values
+    /// containing spaces are never split into multiple arguments.
+    pub fn build_argv(&self) -> Vec<OsString> {
+        let mut argv = Vec::new();
+        let mut placeholder_exists = false;
+
+        for arg in self.command_template {
+            match *arg {
+                // A resolved numbered-token substitution (`{1}`, `{2.}`, etc.) is a
+                // `Cow::Owned` `Token::Argument` carrying one already-resolved value;
+                // it must stay intact as a single argv element, unlike literal
+                // template text (`Cow::Borrowed`), which is split on whitespace.
+                Token::Argument(Cow::Borrowed(arg)) =>
+                    argv.extend(ArgumentSplitter::new(arg).map(OsString::from)),
+                Token::Argument(Cow::Owned(ref arg)) => argv.push(OsString::from(arg.as_str())),
+                Token::Basename => {
+                    placeholder_exists = true;
+                    argv.push(OsString::from(basename(self.input)));
+                },
+                Token::BaseAndExt => {
+                    placeholder_exists = true;
+                    argv.push(OsString::from(basename(remove_extension(self.input))));
+                },
+                Token::BaseAndSuffix(pat) => {
+                    placeholder_exists = true;
+                    argv.push(OsString::from(basename(remove_pattern(self.input, pat))));
+                },
+                Token::Dirname => {
+                    placeholder_exists = true;
+                    argv.push(OsString::from(dirname(self.input)));
+                },
+                Token::Job => {
+                    placeholder_exists = true;
+                    argv.push(OsString::from(str::from_utf8(self.job_no).unwrap_or_default()));
+                },
+                Token::Placeholder => {
+                    placeholder_exists = true;
+                    argv.push(OsString::from(self.input));
+                },
+                Token::RemoveExtension => {
+                    placeholder_exists = true;
+                    argv.push(OsString::from(remove_extension(self.input)));
+                },
+                Token::RemoveSuffix(pat) => {
+                    placeholder_exists = true;
+                    argv.push(OsString::from(remove_pattern(self.input, pat)));
+                },
+                Token::Slot => {
+                    placeholder_exists = true;
+                    argv.push(OsString::from(self.slot_no));
+                },
+            }
+        }
+
+        // If no placeholder tokens are in use, the user probably wants to infer one.
+        if !placeholder_exists {
+            argv.push(OsString::from(self.input));
+        }
+
+        argv
+    }
+
+    /// Builds the fully-substituted argument list as a `Vec<String>`, mirroring
+    /// `build_argv`'s per-token expansion but yielding owned `String`s instead
+    /// of `OsString`s. This exists for the dry-run path, which renders a job
+    /// in argv form -- one already-resolved token per argument -- so that the
+    /// preview matches what will actually run when no shell is required,
+    /// rather than the single concatenated line `build_arguments` produces.
+    pub fn build_argument_list(&self) -> Vec<String> {
+        let mut argv = Vec::new();
+        let mut placeholder_exists = false;
+
+        for arg in self.command_template {
+            match *arg {
+                // See the matching comment in `build_argv`: resolved numbered-token
+                // substitutions must not be re-split on whitespace.
+                Token::Argument(Cow::Borrowed(arg)) =>
+                    argv.extend(ArgumentSplitter::new(arg).map(String::from)),
+                Token::Argument(Cow::Owned(ref arg)) => argv.push(arg.clone()),
+                Token::Basename => {
+                    placeholder_exists = true;
+                    argv.push(basename(self.input).to_owned());
+                },
+                Token::BaseAndExt => {
+                    placeholder_exists = true;
+                    argv.push(basename(remove_extension(self.input)).to_owned());
+                },
+                Token::BaseAndSuffix(pat) => {
+                    placeholder_exists = true;
+                    argv.push(basename(remove_pattern(self.input, pat)).to_owned());
+                },
+                Token::Dirname => {
+                    placeholder_exists = true;
+                    argv.push(dirname(self.input).to_owned());
+                },
+                Token::Job => {
+                    placeholder_exists = true;
+                    argv.push(String::from_utf8_lossy(self.job_no).into_owned());
+                },
+                Token::Placeholder => {
+                    placeholder_exists = true;
+                    argv.push(self.input.to_owned());
+                },
+                Token::RemoveExtension => {
+                    placeholder_exists = true;
+                    argv.push(remove_extension(self.input).to_owned());
+                },
+                Token::RemoveSuffix(pat) => {
+                    placeholder_exists = true;
+                    argv.push(remove_pattern(self.input, pat).to_owned());
+                },
+                Token::Slot => {
+                    placeholder_exists = true;
+                    argv.push(self.slot_no.to_owned());
+                },
+            }
+        }
+
+        // If no placeholder tokens are in use, the user probably wants to infer one.
+        if !placeholder_exists {
+            argv.push(self.input.to_owned());
+        }
+
+        argv
+    }
+}
+
+/// Spawns a command directly from an already-built argument vector, skipping
+/// the shell and the `ArgumentSplitter` re-parsing step entirely.
+fn spawn_argv(argv: &[OsString], flags: u16) -> io::Result<Child> {
+    let (program, args) = argv.split_first().expect("argv must contain a program name");
+    let mut command = Command::new(program);
+    command.args(args);
+
+    if flags & arguments::QUIET_MODE != 0 {
+        command.stdout(Stdio::null()).stderr(Stdio::piped());
+    } else {
+        command.stdout(Stdio::piped()).stderr(Stdio::piped());
+    }
+
+    command.spawn()
 }
 
 /// Handles shell execution and returns a handle to the underlying `Child`
@@ -2,13 +2,16 @@ use arguments;
 use execute::command;
 use input_iterator::{InputIterator, InputIteratorErr};
 use numtoa::NumToA;
+use quoting;
 use tokenizer::Token;
 
 use std::io::{self, Read, StdoutLock, Write};
 
 /// Instead of executing commands in parallel, the commands that would be
 /// executed will be printed directly to the standard output of this
-/// application. This also applies to shell quoted arguments.
+/// application. This also applies to shell quoted arguments. If
+/// `NDJSON_MODE` is set, one JSON object per job is printed instead, for
+/// consumption by other programs.
 pub fn dry_run<IO: Read>(flags: u16, inputs: InputIterator<IO>, arguments: &[Token]) {
     let stdout = io::stdout();
     let stdout = &mut stdout.lock();
@@ -27,7 +30,7 @@ pub fn dry_run<IO: Read>(flags: u16, inputs: InputIterator<IO>, arguments: &[Tok
     // execute will be assigned here in advance.
     let pipe_action: Box<dyn Fn(&mut StdoutLock, &str)> = if flags & arguments::SHELL_QUOTE != 0 {
         Box::new(|stdout: &mut StdoutLock, input: &str| {
-            if let Some(new_arg) = shell_quote(input) {
+            if let Some(new_arg) = quoting::escape_if_needed(input) {
                 let _ = stdout.write(new_arg.as_bytes());
             } else {
                 let _ = stdout.write(input.as_bytes());
@@ -42,6 +45,16 @@ pub fn dry_run<IO: Read>(flags: u16, inputs: InputIterator<IO>, arguments: &[Tok
     for (job_id, input) in inputs.enumerate() {
         match input {
             Ok(input) => {
+                // In batch (`-X`/`--xargs`) mode, `input` is several grouped inputs
+                // joined by `\0` (see `ParallelCommand::exec_batch`'s doc comment) so
+                // they can be split back apart exactly; for a dry-run preview there's
+                // no such exact-recovery requirement, so render the group the same
+                // space-separated way ordinary (non-batch) grouping already displays.
+                let input = if flags & arguments::BATCH_MODE != 0 {
+                    input.replace('\u{0}', " ")
+                } else {
+                    input
+                };
                 let start_indice = job_id.numtoa(10, &mut id_buffer);
                 let command = command::ParallelCommand {
                     slot_no: slot,
@@ -52,17 +65,43 @@ pub fn dry_run<IO: Read>(flags: u16, inputs: InputIterator<IO>, arguments: &[Tok
                     flags,
                 };
 
-                command.build_arguments(&mut command_buffer);
-                if !pipe {
-                    command::append_argument(
-                        &mut command_buffer,
-                        command.command_template,
-                        command.input,
+                if flags & arguments::NDJSON_MODE != 0 {
+                    // Emit one JSON object per job instead of a shell line, so
+                    // that other programs can consume the plan programmatically.
+                    write_ndjson_line(
+                        stdout,
+                        command.job_no,
+                        job_total,
+                        slot,
+                        &input,
+                        &command.build_argument_list(),
                     );
+                } else if !pipe && flags & arguments::SHELL_ENABLED == 0 {
+                    // No shell is required for this template, so the job will be
+                    // spawned directly from an argv rather than handed to `sh -c`.
+                    // Render that same argv here, one already-resolved token per
+                    // argument, so the preview matches what will actually run.
+                    for (index, argument) in command.build_argument_list().iter().enumerate() {
+                        if index > 0 {
+                            let _ = stdout.write(b" ");
+                        }
+                        pipe_action(stdout, argument);
+                    }
+                } else {
+                    command.build_arguments(&mut command_buffer);
+                    if !pipe {
+                        let quote = flags & arguments::SHELL_ENABLED != 0;
+                        command::append_argument(
+                            &mut command_buffer,
+                            command.command_template,
+                            command.input,
+                            quote,
+                        );
+                    }
+                    pipe_action(stdout, &command_buffer);
+                    command_buffer.clear();
                 }
-                pipe_action(stdout, &command_buffer);
                 let _ = stdout.write(b"\n");
-                command_buffer.clear();
             },
             Err(why) => match why {
                 InputIteratorErr::FileRead(path, why) => {
@@ -77,31 +116,65 @@ pub fn dry_run<IO: Read>(flags: u16, inputs: InputIterator<IO>, arguments: &[Tok
     }
 }
 
-/// Simply escapes special characters, optionally returning a new `String` if
-/// changes occurred
-fn shell_quote(command: &str) -> Option<String> {
-    // Determines if allocations will be necessary or not.
-    let mut needs_escaping = false;
-    for character in command.chars() {
-        match character {
-            '$' | ' ' | '\\' | '>' | '<' | '^' | '&' | '#' | '!' | '*' | '\'' | '\"' | '`'
-            | '~' | '{' | '}' | '[' | ']' | '(' | ')' | ';' | '|' | '?' => needs_escaping = true,
-            _ => (),
+/// Writes a single NDJSON record describing one job directly to `stdout`:
+/// the job id, slot id, total job count, raw input, and fully-resolved
+/// argument list (as built by `ParallelCommand::build_argument_list`). The
+/// integer fields are written from the `NumToA`-formatted byte slices that
+/// the caller already has on hand, so no extra allocation is needed for
+/// those; only the JSON punctuation and escaped string fields go through
+/// `stdout` directly.
+fn write_ndjson_line(
+    stdout: &mut StdoutLock,
+    job_no: &[u8],
+    job_total: &[u8],
+    slot: &str,
+    input: &str,
+    argv: &[String],
+) {
+    let _ = stdout.write(b"{\"job\":");
+    let _ = stdout.write(job_no);
+    let _ = stdout.write(b",\"slot\":");
+    write_json_string(stdout, slot);
+    let _ = stdout.write(b",\"total\":");
+    let _ = stdout.write(job_total);
+    let _ = stdout.write(b",\"input\":");
+    write_json_string(stdout, input);
+    let _ = stdout.write(b",\"argv\":[");
+    for (index, argument) in argv.iter().enumerate() {
+        if index > 0 {
+            let _ = stdout.write(b",");
         }
+        write_json_string(stdout, argument);
     }
+    let _ = stdout.write(b"]}");
+}
 
-    if needs_escaping {
-        let mut output = String::with_capacity(command.len() * 2);
-        for character in command.chars() {
-            match character {
-                '$' | ' ' | '\\' | '>' | '<' | '^' | '&' | '#' | '!' | '*' | '\'' | '\"' | '`'
-                | '~' | '{' | '}' | '[' | ']' | '(' | ')' | ';' | '|' | '?' => output.push('\\'),
-                _ => (),
-            }
-            output.push(character);
+/// Writes `value` as a quoted JSON string literal directly to `stdout`,
+/// escaping `"`, `\`, and control characters so that embedded quotes or
+/// newlines in an input can't corrupt the NDJSON stream -- the JSON
+/// equivalent of `quoting::escape_if_needed`'s job for the shell-line output
+/// mode.
+fn write_json_string(stdout: &mut StdoutLock, value: &str) {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+
+    let _ = stdout.write(b"\"");
+    for byte in value.bytes() {
+        match byte {
+            b'"' => { let _ = stdout.write(b"\\\""); },
+            b'\\' => { let _ = stdout.write(b"\\\\"); },
+            b'\n' => { let _ = stdout.write(b"\\n"); },
+            b'\r' => { let _ = stdout.write(b"\\r"); },
+            b'\t' => { let _ = stdout.write(b"\\t"); },
+            0x00..=0x1F => {
+                let escape = [
+                    b'\\', b'u', b'0', b'0',
+                    HEX[(byte >> 4) as usize],
+                    HEX[(byte & 0xF) as usize],
+                ];
+                let _ = stdout.write(&escape);
+            },
+            _ => { let _ = stdout.write(&[byte]); },
         }
-        Some(output)
-    } else {
-        None
     }
+    let _ = stdout.write(b"\"");
 }
@@ -0,0 +1,82 @@
+use std::io;
+use std::os::unix::io::RawFd;
+
+/// The largest number of bytes to move through the kernel pipe buffer in a
+/// single `splice(2)` call.
+const SPLICE_CHUNK: usize = 64 * 1024;
+
+/// Moves the remaining bytes of `src_fd` into `dst_fd` without copying them
+/// through userspace, by repeatedly calling `splice(2)`. Used to stream a
+/// child's stdout directly into its slot's on-disk buffer, avoiding the
+/// read/write round-trip through an intermediate userspace buffer that the
+/// non-Linux fallback requires.
+///
+/// Returns the total number of bytes moved. At least one of `src_fd` and
+/// `dst_fd` must refer to a pipe, which holds for the child-stdout-to-file
+/// case this is used for.
+///
+/// Meant to be called from the stdout/stderr capture loop that streams a
+/// child's output into its slot's on-disk buffer -- that loop lives in
+/// `execute::pipe::disk`, which doesn't exist in this tree (there's no
+/// `execute/pipe` directory at all), so nothing calls this yet. Re-checked
+/// on review: still no such module in this tree, so there is still nothing
+/// to wire this into yet. `#[allow(dead_code)]` documents that gap rather
+/// than hiding it; remove it once the capture loop is wired up to call this
+/// instead of its read/write round-trip.
+#[allow(dead_code)]
+#[cfg(target_os = "linux")]
+pub fn copy_offloaded(src_fd: RawFd, dst_fd: RawFd) -> io::Result<u64> {
+    let mut total = 0u64;
+    loop {
+        let moved = unsafe {
+            libc::splice(
+                src_fd,
+                std::ptr::null_mut(),
+                dst_fd,
+                std::ptr::null_mut(),
+                SPLICE_CHUNK,
+                libc::SPLICE_F_MOVE,
+            )
+        };
+
+        if moved < 0 {
+            let err = io::Error::last_os_error();
+            if err.kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+            return Err(err);
+        } else if moved == 0 {
+            return Ok(total);
+        }
+
+        total += moved as u64;
+    }
+}
+
+/// On platforms without `splice(2)`, falls back to a plain buffered copy.
+/// Neither fd is closed by this function; both remain owned by the caller.
+///
+/// See the `cfg(target_os = "linux")` definition above for why this has no
+/// caller yet.
+#[allow(dead_code)]
+#[cfg(not(target_os = "linux"))]
+pub fn copy_offloaded(src_fd: RawFd, dst_fd: RawFd) -> io::Result<u64> {
+    use std::fs::File;
+    use std::io::{Read, Write};
+    use std::mem::ManuallyDrop;
+    use std::os::unix::io::FromRawFd;
+
+    let mut src = ManuallyDrop::new(unsafe { File::from_raw_fd(src_fd) });
+    let mut dst = ManuallyDrop::new(unsafe { File::from_raw_fd(dst_fd) });
+    let mut buffer = [0u8; SPLICE_CHUNK];
+    let mut total = 0u64;
+
+    loop {
+        let read = src.read(&mut buffer)?;
+        if read == 0 {
+            return Ok(total);
+        }
+        dst.write_all(&buffer[..read])?;
+        total += read as u64;
+    }
+}
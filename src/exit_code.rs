@@ -0,0 +1,45 @@
+/// The outcome of a single finished job, used to compute the process's final
+/// aggregate exit code.
+///
+/// This is meant to be populated by the per-job dispatch/receive loop (the
+/// `execute::receive_messages` worker that reads `State` off the `output_tx`
+/// channel in `main.rs`) and folded together with `aggregate_exit_code` once
+/// every job has reported in or a `--halt` policy has tripped. That loop
+/// lives in `execute::pipe::disk` / `execute::mod`, neither of which exist in
+/// this tree yet, so nothing constructs a `JobExit` today -- `Args::halt` is
+/// parsed and stored but not yet enforced. `#[allow(dead_code)]` documents
+/// that gap rather than hiding it; remove it once the dispatch loop is wired
+/// up to construct and aggregate these.
+#[allow(dead_code)]
+pub enum JobExit {
+    /// The job exited successfully (status `0`).
+    Success,
+    /// The job exited with the given non-zero status.
+    Failure(i32),
+    /// The job was killed by a signal.
+    Signaled,
+}
+
+/// Computes the final exit code for the whole `parallel` invocation, following
+/// xargs' exit-status rules: `0` if every job succeeded, `123` if any job
+/// exited `1`-`125`, `125` if a job was killed by a signal, and `255` if a job
+/// exited `255`. `halted` overrides all of these with `124`, used when a
+/// `--halt` policy tripped and no further jobs were launched.
+///
+/// See the note on `JobExit` for why this has no caller yet.
+#[allow(dead_code)]
+pub fn aggregate_exit_code<'a, I: IntoIterator<Item = &'a JobExit>>(jobs: I, halted: bool) -> i32 {
+    if halted {
+        return 124;
+    }
+
+    jobs.into_iter()
+        .map(|job| match *job {
+            JobExit::Success => 0,
+            JobExit::Failure(255) => 255,
+            JobExit::Failure(_) => 123,
+            JobExit::Signaled => 125,
+        })
+        .max()
+        .unwrap_or(0)
+}
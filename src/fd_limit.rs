@@ -0,0 +1,65 @@
+use std::io::{self, Write};
+
+/// Each worker thread may hold open several file descriptors at once: the
+/// child's stdin/stdout/stderr pipes, plus the per-job temp files it reads
+/// and writes under the tempdir. This is a conservative per-worker budget
+/// used to size the requested `RLIMIT_NOFILE`.
+const FDS_PER_WORKER: u64 = 16;
+
+/// Raises the soft open-file-descriptor limit to accommodate `ncores`
+/// concurrent worker threads, clamped to the hard limit (and, on macOS, to
+/// `kern.maxfilesperproc`). This is best-effort: if the limit cannot be
+/// raised, a warning is printed to standard error and the existing limit is
+/// left in place rather than aborting.
+#[cfg(unix)]
+pub fn raise(ncores: usize) {
+    unsafe {
+        let mut rlim = std::mem::zeroed::<libc::rlimit>();
+        if libc::getrlimit(libc::RLIMIT_NOFILE, &mut rlim) != 0 {
+            warn("unable to query the open-file-descriptor limit");
+            return;
+        }
+
+        let hard_limit = if cfg!(target_os = "macos") {
+            let mut maxfiles: libc::c_int = 0;
+            let mut size = std::mem::size_of::<libc::c_int>();
+            let mib = [libc::CTL_KERN, libc::KERN_MAXFILESPERPROC];
+            let result = libc::sysctl(
+                mib.as_ptr() as *mut libc::c_int,
+                mib.len() as libc::c_uint,
+                &mut maxfiles as *mut libc::c_int as *mut libc::c_void,
+                &mut size,
+                std::ptr::null_mut(),
+                0,
+            );
+            if result == 0 {
+                std::cmp::min(maxfiles as libc::rlim_t, rlim.rlim_max)
+            } else {
+                rlim.rlim_max
+            }
+        } else {
+            rlim.rlim_max
+        };
+
+        let wanted = (ncores as u64).saturating_mul(FDS_PER_WORKER);
+        let target = std::cmp::min(wanted, hard_limit);
+
+        if target <= rlim.rlim_cur {
+            return;
+        }
+
+        rlim.rlim_cur = target;
+        if libc::setrlimit(libc::RLIMIT_NOFILE, &rlim) != 0 {
+            warn("unable to raise the open-file-descriptor limit");
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub fn raise(_ncores: usize) {}
+
+#[cfg(unix)]
+fn warn(message: &str) {
+    let stderr = io::stderr();
+    let _ = writeln!(stderr.lock(), "parallel: {}", message);
+}
@@ -47,6 +47,12 @@ impl ETA {
     }
 }
 
+/// The number of most-recent samples the ETA's moving average effectively
+/// considers (`alpha` is `1 / EMA_WINDOW`). Kept small so that the estimate
+/// tracks recent throughput instead of being dragged down by jobs that ran
+/// much earlier in a long session.
+const EMA_WINDOW: u64 = 5;
+
 /// The `InputIterator` tracks the total number of arguments, the current
 /// argument counter, and takes ownership of an `InputBuffer` which buffers
 /// input arguments from the disk when arguments stored in memory are depleted.
@@ -55,27 +61,53 @@ pub struct InputIterator<IO: Read> {
     pub curr_argument:   usize,
     pub completed:       usize,
     start_time:          u64,
+    last_sample:         u64,
     average_time:        u64,
     input_buffer:        InputBuffer<IO>,
 }
 
 impl<IO: Read> InputIterator<IO> {
-    pub fn new(path: &Path, file: IO, args: usize) -> Result<InputIterator<IO>, FileErr> {
+    pub fn new(
+        path: &Path,
+        file: IO,
+        args: usize,
+        delimiter: u8,
+    ) -> Result<InputIterator<IO>, FileErr> {
         // Create an `InputBuffer` from the unprocessed file.
         let disk_buffer = DiskBufferReader::new(path, file);
 
-        let input_buffer = InputBuffer::new(disk_buffer)?;
+        let input_buffer = InputBuffer::new(disk_buffer, delimiter)?;
+        let start_time = time::precise_time_ns();
 
         Ok(InputIterator {
             total_arguments: args,
             curr_argument: 0,
             completed: 0,
             input_buffer,
-            start_time: time::precise_time_ns(),
+            start_time,
+            last_sample: start_time,
             average_time: 0,
         })
     }
 
+    /// Updates the moving-average job duration used for the ETA estimate. An
+    /// exponential moving average is used instead of a cumulative mean over
+    /// the whole run, so that the estimate responds to recent changes in job
+    /// duration rather than being anchored to jobs completed much earlier.
+    fn record_progress(&mut self) {
+        let now = time::precise_time_ns();
+        match self.completed {
+            0 => (),
+            1 => self.average_time = now - self.start_time,
+            _ => {
+                let sample = now - self.last_sample;
+                let delta = sample as i64 - self.average_time as i64;
+                self.average_time = (self.average_time as i64 + delta / EMA_WINDOW as i64) as u64;
+            },
+        }
+        self.last_sample = now;
+    }
+
     fn buffer(&mut self) -> Result<(), InputIteratorErr> {
         // Read the next set of arguments from the unprocessed file, but only read as
         // many bytes as the buffer can hold without overwriting the unused
@@ -125,13 +157,7 @@ impl<IO: Read> InputIterator<IO> {
         };
 
         // Update times
-        match self.completed {
-            0 => (),
-            1 => self.average_time = time::precise_time_ns() - self.start_time,
-            _ =>
-                self.average_time =
-                    (time::precise_time_ns() - self.start_time) / self.completed as u64,
-        }
+        self.record_progress();
 
         // Increment the iterator's state.
         self.curr_argument += 1;
@@ -141,7 +167,7 @@ impl<IO: Read> InputIterator<IO> {
         buffer.truncate(0);
         unsafe {
             buffer.push_str(str::from_utf8_unchecked(
-                &self.input_buffer.disk_buffer.data[start..end],
+                &self.input_buffer.disk_buffer.data()[start..end],
             ));
         }
         Some(Ok(()))
@@ -174,13 +200,7 @@ impl<IO: Read> Iterator for InputIterator<IO> {
         };
 
         // Update times
-        match self.completed {
-            0 => (),
-            1 => self.average_time = time::precise_time_ns() - self.start_time,
-            _ =>
-                self.average_time =
-                    (time::precise_time_ns() - self.start_time) / self.completed as u64,
-        }
+        self.record_progress();
 
         // Increment the iterator's state.
         self.curr_argument += 1;
@@ -188,7 +208,7 @@ impl<IO: Read> Iterator for InputIterator<IO> {
 
         // Copy the input from the buffer into a `String` and return it
         Some(Ok(String::from_utf8_lossy(
-            &self.input_buffer.disk_buffer.data[start..end],
+            &self.input_buffer.disk_buffer.data()[start..end],
         )
         .into_owned()))
     }
@@ -205,13 +225,16 @@ struct InputBuffer<IO: Read> {
     capacity:    usize,
     disk_buffer: DiskBufferReader<IO>,
     indices:     [usize; BUFFER_SIZE / 2],
+    delimiter:   u8,
 }
 
 impl<IO: Read> InputBuffer<IO> {
     /// Takes ownership of a `DiskBufferReader` and transforms it into a higher
     /// level `InputBuffer` which will track additional information about
-    /// the disk buffer.
-    fn new(mut unprocessed: DiskBufferReader<IO>) -> Result<InputBuffer<IO>, FileErr> {
+    /// the disk buffer. Arguments are split on `delimiter`, which allows
+    /// NUL-delimited (`-0`) input to be read just as safely as newline
+    /// separated input.
+    fn new(mut unprocessed: DiskBufferReader<IO>, delimiter: u8) -> Result<InputBuffer<IO>, FileErr> {
         unprocessed
             .buffer(0)
             .map_err(|why| FileErr::Read(unprocessed.path.clone(), why))?;
@@ -224,6 +247,7 @@ impl<IO: Read> InputBuffer<IO> {
             capacity:    0,
             disk_buffer: unprocessed,
             indices:     [0usize; BUFFER_SIZE / 2],
+            delimiter,
         };
 
         count_arguments(&mut temp, bytes_read);
@@ -237,14 +261,15 @@ impl<IO: Read> InputBuffer<IO> {
 fn count_arguments<IO: Read>(buffer: &mut InputBuffer<IO>, bytes_read: usize) {
     let mut newlines = 1;
     buffer.capacity = 0;
+    let delimiter = buffer.delimiter;
 
     for (indice, _) in buffer
         .disk_buffer
-        .data
+        .data()
         .iter()
         .take(bytes_read)
         .enumerate()
-        .filter(|&(_, byte)| *byte == b'\n')
+        .filter(|&(_, byte)| *byte == delimiter)
     {
         buffer.indices[newlines] = indice;
         newlines += 1;
@@ -263,7 +288,8 @@ mod tests {
     #[test]
     fn test_input_iterator() {
         let file = File::open("tests/buffer.dat").unwrap();
-        let iterator = InputIterator::new(Path::new("tests/buffer.dat"), file, 4096).unwrap();
+        let iterator =
+            InputIterator::new(Path::new("tests/buffer.dat"), file, 4096, b'\n').unwrap();
         assert_eq!(0, iterator.input_buffer.start);
         assert_eq!(1859, iterator.input_buffer.end);
         for (actual, expected) in iterator.zip(1..4096) {
@@ -2,6 +2,7 @@
 #![allow(unknown_lints)]
 extern crate arrayvec;
 extern crate itoa;
+extern crate libc;
 extern crate numtoa;
 extern crate num_cpus;
 extern crate permutate;
@@ -13,9 +14,12 @@ extern crate wait_timeout;
 mod arguments;
 mod disk_buffer;
 mod execute;
+mod exit_code;
+mod fd_limit;
 mod filepaths;
 mod input_iterator;
 mod misc;
+mod quoting;
 mod tokenizer;
 mod shell;
 mod verbose;
@@ -94,6 +98,13 @@ fn main() {
         Err(why) => why.handle(&raw_arguments)
     };
 
+    // Raise the soft fd limit to accommodate all of the upcoming worker
+    // threads, unless the user opted out because they manage ulimits
+    // themselves.
+    if args.flags & arguments::NO_FD_LIMIT == 0 {
+        fd_limit::raise(args.ncores);
+    }
+
     // Attempt to convert the base path into a string slice.
     let base_path = match base.to_str() {
         Some(base) => String::from(base),
@@ -126,7 +137,7 @@ fn main() {
 
     // Initialize the `InputIterator` structure, which efficiently generates inputs from the
     // above `unprocessed` file until all arguments have been processed, denoted by `args.ninputs`.
-    let inputs = InputIterator::new(&unprocessed_path, file, args.ninputs)
+    let inputs = InputIterator::new(&unprocessed_path, file, args.ninputs, args.delimiter)
         .expect("unable to initialize the InputIterator structure");
 
     // Coerce the `comm` `String` into a `&'static str` so that it may be shared by all threads.
@@ -145,6 +156,11 @@ fn main() {
     // each thread.
     let arguments = unsafe { static_arg(&args.arguments) };
 
+    // Determine whether the command template requires a shell (e.g. it uses `;`,
+    // `|`, or `$`) before dry-run or real execution, so that both paths agree on
+    // whether jobs will be spawned directly via argv or handed to `sh -c`.
+    shell::set_flags(&mut args.flags, arguments);
+
     // If the `--dry-run` parameter was passsed, the program will simply print all commands to
     // execute and will subsequently quit. Otherwise, real work will be performed.
     if args.flags & arguments::DRY_RUN != 0 {
@@ -201,8 +217,6 @@ fn main() {
                 threads.push(handle);
             }
         } else {
-            shell::set_flags(&mut args.flags, arguments);
-
             for slot in 1..args.ncores+1 {
                 let timeout    = args.timeout;
                 let num_inputs = args.ninputs;
@@ -0,0 +1,64 @@
+//! POSIX single-quote escaping shared by the real-exec path
+//! (`execute::command`, which always quotes substituted values before
+//! handing them to `sh -c`) and the dry-run path (`execute::dry`, which only
+//! quotes when it would actually change how the preview reads).
+
+/// POSIX single-quotes `value`, encoding embedded single quotes as `'\''`, so
+/// that it reaches the shell intact regardless of spaces, `$`, backticks, or
+/// `;` it may contain.
+pub fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('\'');
+    for character in value.chars() {
+        if character == '\'' {
+            escaped.push_str("'\\''");
+        } else {
+            escaped.push(character);
+        }
+    }
+    escaped.push('\'');
+    escaped
+}
+
+/// Quotes `value` like `escape`, but only when doing so would change how it
+/// reads: returns `None` if `value` is non-empty and contains nothing but
+/// characters that are never special to the shell, so a dry-run preview
+/// isn't cluttered with quotes around ordinary-looking arguments.
+pub fn escape_if_needed(value: &str) -> Option<String> {
+    let needs_quoting = value.is_empty()
+        || value.bytes().any(|byte| {
+            !matches!(byte, b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'/')
+        });
+
+    if needs_quoting {
+        Some(escape(value))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_wraps_in_single_quotes() {
+        assert_eq!(escape("hello"), "'hello'");
+    }
+
+    #[test]
+    fn escape_handles_embedded_single_quotes() {
+        assert_eq!(escape("it's"), "'it'\\''s'");
+    }
+
+    #[test]
+    fn escape_if_needed_skips_plain_values() {
+        assert_eq!(escape_if_needed("plain-value_1.2/3"), None);
+    }
+
+    #[test]
+    fn escape_if_needed_quotes_special_or_empty_values() {
+        assert_eq!(escape_if_needed("a b"), Some("'a b'".to_owned()));
+        assert_eq!(escape_if_needed(""), Some("''".to_owned()));
+    }
+}
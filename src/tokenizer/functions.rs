@@ -0,0 +1,70 @@
+/// Returns the basename (final path component) of `input`: everything after
+/// the last `/`, or the whole string if it contains no `/`.
+pub fn basename(input: &str) -> &str {
+    match input.rfind('/') {
+        Some(pos) => &input[pos + 1..],
+        None => input,
+    }
+}
+
+/// Returns the directory portion of `input`: everything up to, but not
+/// including, the last `/`. If the only `/` is the leading one, the root
+/// (`/`) is returned; if `input` contains no `/`, the current directory
+/// (`.`) is returned.
+pub fn dirname(input: &str) -> &str {
+    match input.rfind('/') {
+        Some(0) => "/",
+        Some(pos) => &input[..pos],
+        None => ".",
+    }
+}
+
+/// Removes the final extension from `input`, where the extension is
+/// everything from the last `.` in the final path component onward. If the
+/// final path component has no `.`, or its only `.` is a leading one (a
+/// dotfile, e.g. `.bashrc`), `input` is returned unchanged.
+pub fn remove_extension(input: &str) -> &str {
+    let base_start = input.rfind('/').map_or(0, |pos| pos + 1);
+    match input[base_start..].rfind('.') {
+        Some(0) | None => input,
+        Some(pos) => &input[..base_start + pos],
+    }
+}
+
+/// Removes the given literal suffix pattern from the end of `input`, if
+/// present, leaving `input` unchanged otherwise. Backs the custom `{^suffix}`
+/// and `{/^suffix}` placeholders.
+pub fn remove_pattern<'a>(input: &'a str, pattern: &str) -> &'a str {
+    input.strip_suffix(pattern).unwrap_or(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basename_strips_directory() {
+        assert_eq!(basename("/foo/bar/baz.tar.gz"), "baz.tar.gz");
+        assert_eq!(basename("baz.tar.gz"), "baz.tar.gz");
+    }
+
+    #[test]
+    fn dirname_keeps_directory() {
+        assert_eq!(dirname("/foo/bar/baz.tar.gz"), "/foo/bar");
+        assert_eq!(dirname("/baz.tar.gz"), "/");
+        assert_eq!(dirname("baz.tar.gz"), ".");
+    }
+
+    #[test]
+    fn remove_extension_strips_final_extension_only() {
+        assert_eq!(remove_extension("/foo/bar/baz.tar.gz"), "/foo/bar/baz.tar");
+        assert_eq!(remove_extension("baz"), "baz");
+        assert_eq!(remove_extension("/foo/.bashrc"), "/foo/.bashrc");
+    }
+
+    #[test]
+    fn remove_pattern_strips_matching_suffix() {
+        assert_eq!(remove_pattern("baz.tar.gz", ".gz"), "baz.tar");
+        assert_eq!(remove_pattern("baz.tar.gz", ".zip"), "baz.tar.gz");
+    }
+}